@@ -0,0 +1,162 @@
+use crate::{collect_screenshot_items, screenshot_dir, ScreenshotItem};
+use serde::{Deserialize, Serialize};
+use time::{format_description::well_known::Rfc3339, Duration, OffsetDateTime};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupCriteria {
+    /// Only screenshots created at least this many days ago.
+    older_than_days: Option<u32>,
+    /// Only screenshots at least this many bytes.
+    min_size_bytes: Option<u64>,
+    /// Only screenshots not modified (a proxy for "not opened") in this many days.
+    not_opened_days: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CleanupResult {
+    candidates: Vec<ScreenshotItem>,
+    total_reclaimable_bytes: u64,
+}
+
+fn parse_rfc3339(s: &Option<String>) -> Option<OffsetDateTime> {
+    s.as_deref().and_then(|s| OffsetDateTime::parse(s, &Rfc3339).ok())
+}
+
+fn matches_criteria(item: &ScreenshotItem, criteria: &CleanupCriteria, now: OffsetDateTime) -> bool {
+    if let Some(min_size) = criteria.min_size_bytes {
+        if item.size_bytes.unwrap_or(0) < min_size {
+            return false;
+        }
+    }
+    if let Some(days) = criteria.older_than_days {
+        let cutoff = now - Duration::days(days as i64);
+        match parse_rfc3339(&item.created_at) {
+            Some(created) if created <= cutoff => {}
+            _ => return false,
+        }
+    }
+    if let Some(days) = criteria.not_opened_days {
+        let cutoff = now - Duration::days(days as i64);
+        match parse_rfc3339(&item.modified_at) {
+            Some(modified) if modified <= cutoff => {}
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Surfaces screenshots matching age/size rules (mirroring a "free up space"
+/// temp-file finder), pre-sorted biggest-and-oldest space hogs first, along
+/// with the aggregate reclaimable bytes so the UI can show the payoff before
+/// the user commits. Feeds directly into `delete_to_trash`.
+#[tauri::command]
+pub fn find_cleanup_candidates(criteria: CleanupCriteria) -> tauri::Result<CleanupResult> {
+    let shots_dir = screenshot_dir().ok_or_else(|| anyhow::anyhow!("No screenshots directory found"))?;
+    let items = collect_screenshot_items(&shots_dir).map_err(|e| anyhow::anyhow!(e))?;
+
+    let now = OffsetDateTime::now_utc();
+    let mut candidates: Vec<ScreenshotItem> =
+        items.into_iter().filter(|item| matches_criteria(item, &criteria, now)).collect();
+
+    // Biggest first; among same-size files, oldest (least recently created) first.
+    candidates.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.created_at.cmp(&b.created_at)));
+
+    let total_reclaimable_bytes: u64 = candidates.iter().filter_map(|i| i.size_bytes).sum();
+
+    Ok(CleanupResult { candidates, total_reclaimable_bytes })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(now: OffsetDateTime, created_days_ago: i64, modified_days_ago: i64, size_bytes: u64) -> ScreenshotItem {
+        ScreenshotItem {
+            path: "a.png".to_string(),
+            file_name: "a.png".to_string(),
+            created_at: Some((now - Duration::days(created_days_ago)).format(&Rfc3339).unwrap()),
+            modified_at: Some((now - Duration::days(modified_days_ago)).format(&Rfc3339).unwrap()),
+            size_bytes: Some(size_bytes),
+        }
+    }
+
+    fn criteria(older_than_days: Option<u32>, min_size_bytes: Option<u64>, not_opened_days: Option<u32>) -> CleanupCriteria {
+        CleanupCriteria { older_than_days, min_size_bytes, not_opened_days }
+    }
+
+    #[test]
+    fn older_than_days_excludes_items_younger_than_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        let recent = item(now, 1, 1, 10);
+        assert!(!matches_criteria(&recent, &criteria(Some(10), None, None), now));
+    }
+
+    #[test]
+    fn older_than_days_includes_items_at_or_past_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        let at_cutoff = item(now, 10, 10, 10);
+        let past_cutoff = item(now, 20, 20, 10);
+        assert!(matches_criteria(&at_cutoff, &criteria(Some(10), None, None), now));
+        assert!(matches_criteria(&past_cutoff, &criteria(Some(10), None, None), now));
+    }
+
+    #[test]
+    fn min_size_bytes_excludes_smaller_and_includes_equal_or_larger() {
+        let now = OffsetDateTime::now_utc();
+        let smaller = item(now, 0, 0, 99);
+        let equal = item(now, 0, 0, 100);
+        let larger = item(now, 0, 0, 101);
+        assert!(!matches_criteria(&smaller, &criteria(None, Some(100), None), now));
+        assert!(matches_criteria(&equal, &criteria(None, Some(100), None), now));
+        assert!(matches_criteria(&larger, &criteria(None, Some(100), None), now));
+    }
+
+    #[test]
+    fn min_size_bytes_treats_missing_size_as_zero() {
+        let now = OffsetDateTime::now_utc();
+        let mut no_size = item(now, 0, 0, 0);
+        no_size.size_bytes = None;
+        assert!(!matches_criteria(&no_size, &criteria(None, Some(1), None), now));
+        assert!(matches_criteria(&no_size, &criteria(None, Some(0), None), now));
+    }
+
+    #[test]
+    fn not_opened_days_excludes_items_modified_after_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        let recent = item(now, 30, 1, 10);
+        assert!(!matches_criteria(&recent, &criteria(None, None, Some(10)), now));
+    }
+
+    #[test]
+    fn not_opened_days_includes_items_modified_at_or_past_cutoff() {
+        let now = OffsetDateTime::now_utc();
+        let at_cutoff = item(now, 30, 10, 10);
+        let past_cutoff = item(now, 30, 20, 10);
+        assert!(matches_criteria(&at_cutoff, &criteria(None, None, Some(10)), now));
+        assert!(matches_criteria(&past_cutoff, &criteria(None, None, Some(10)), now));
+    }
+
+    #[test]
+    fn missing_timestamp_fails_a_date_based_criterion() {
+        let now = OffsetDateTime::now_utc();
+        let mut no_created = item(now, 30, 30, 10);
+        no_created.created_at = None;
+        assert!(!matches_criteria(&no_created, &criteria(Some(10), None, None), now));
+
+        let mut no_modified = item(now, 30, 30, 10);
+        no_modified.modified_at = None;
+        assert!(!matches_criteria(&no_modified, &criteria(None, None, Some(10)), now));
+    }
+
+    #[test]
+    fn all_criteria_unset_matches_everything() {
+        let now = OffsetDateTime::now_utc();
+        let mut bare = item(now, 0, 0, 0);
+        bare.created_at = None;
+        bare.modified_at = None;
+        bare.size_bytes = None;
+        assert!(matches_criteria(&bare, &criteria(None, None, None), now));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{collect_screenshot_items, screenshot_dir, ScreenshotItem};
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tauri::AppHandle;
+
+const STAGE_HASHING: u32 = 1;
+const STAGE_GROUPING: u32 = 2;
+const TOTAL_STAGES: u32 = 2;
+
+const DEFAULT_HAMMING_THRESHOLD: u32 = 10;
+// dHash: an 8x8 grid of "is this pixel brighter than its right neighbor" bits,
+// so the source row needs one extra column.
+const HASH_WIDTH: u32 = 9;
+const HASH_HEIGHT: u32 = 8;
+
+// Perceptual difference hash (dHash) of an image, packed into 64 bits.
+// Returns None if the file can't be decoded (caller skips it, no group).
+fn dhash(path: &PathBuf) -> Option<u64> {
+    let img = image::open(path)
+        .ok()?
+        .resize_exact(HASH_WIDTH, HASH_HEIGHT, image::imageops::FilterType::Triangle)
+        .into_luma8();
+
+    let mut hash: u64 = 0;
+    for y in 0..HASH_HEIGHT {
+        for x in 0..(HASH_WIDTH - 1) {
+            let left = img.get_pixel(x, y)[0];
+            let right = img.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | (left > right) as u64;
+        }
+    }
+    Some(hash)
+}
+
+fn find(parent: &mut [usize], i: usize) -> usize {
+    if parent[i] != i {
+        parent[i] = find(parent, parent[i]);
+    }
+    parent[i]
+}
+
+fn union(parent: &mut [usize], a: usize, b: usize) {
+    let (ra, rb) = (find(parent, a), find(parent, b));
+    if ra != rb {
+        parent[ra] = rb;
+    }
+}
+
+// Unions hashed items into duplicate groups (Hamming distance <= threshold),
+// dropping singletons, and sorts each group largest/most-recent first. Pure
+// and I/O-free so it's unit-testable without decoding real images.
+fn group_hashed(hashed: Vec<(ScreenshotItem, u64)>, threshold: u32) -> Vec<Vec<ScreenshotItem>> {
+    let mut parent: Vec<usize> = (0..hashed.len()).collect();
+    for i in 0..hashed.len() {
+        for j in (i + 1)..hashed.len() {
+            if (hashed[i].1 ^ hashed[j].1).count_ones() <= threshold {
+                union(&mut parent, i, j);
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<ScreenshotItem>> = HashMap::new();
+    for (i, (item, _)) in hashed.into_iter().enumerate() {
+        let root = find(&mut parent, i);
+        groups.entry(root).or_default().push(item);
+    }
+
+    let mut result: Vec<Vec<ScreenshotItem>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    for group in &mut result {
+        group.sort_by(|a, b| {
+            b.size_bytes
+                .cmp(&a.size_bytes)
+                .then_with(|| b.modified_at.cmp(&a.modified_at))
+        });
+    }
+    result
+}
+
+fn scan_and_group(
+    app: &AppHandle,
+    job_id: &str,
+    cancel: &std::sync::Arc<std::sync::atomic::AtomicBool>,
+    threshold: u32,
+) -> anyhow::Result<Vec<Vec<ScreenshotItem>>> {
+    let shots_dir = screenshot_dir().ok_or_else(|| anyhow::anyhow!("No screenshots directory found"))?;
+    let items = collect_screenshot_items(&shots_dir).map_err(|e| anyhow::anyhow!(e))?;
+
+    // Decoding is the expensive part, so hash in parallel; a decode failure
+    // just drops that file rather than failing the whole scan.
+    let checked = AtomicU64::new(0);
+    let hashed: Vec<(ScreenshotItem, u64)> = items
+        .into_par_iter()
+        .filter_map(|item| {
+            if cancel.load(Ordering::SeqCst) {
+                return None;
+            }
+            let hash = dhash(&PathBuf::from(&item.path));
+            let n = checked.fetch_add(1, Ordering::SeqCst) + 1;
+            if n % 25 == 0 {
+                crate::jobs::emit_progress(app, job_id, STAGE_HASHING, TOTAL_STAGES, n);
+            }
+            Some((item, hash?))
+        })
+        .collect();
+    crate::jobs::emit_progress(app, job_id, STAGE_HASHING, TOTAL_STAGES, checked.load(Ordering::SeqCst));
+
+    let hashed_count = hashed.len() as u64;
+    let result = group_hashed(hashed, threshold);
+    crate::jobs::emit_progress(app, job_id, STAGE_GROUPING, TOTAL_STAGES, hashed_count);
+
+    Ok(result)
+}
+
+/// Groups visually near-identical screenshots (repeated captures, before/after
+/// crops, Retina vs. scaled copies) by perceptual hash on a worker thread,
+/// reporting progress via `job-progress` and the groups via `job-complete`
+/// (both keyed by `job_id`). Two screenshots land in the same group when their
+/// dHash Hamming distance is <= `threshold` (default 10). Each group is sorted
+/// largest/most-recent first, i.e. the suggested "keep". Cancellable through
+/// `cancel_job`.
+#[tauri::command]
+pub fn find_duplicate_screenshots(app: AppHandle, job_id: String, threshold: Option<u32>) -> tauri::Result<()> {
+    let threshold = threshold.unwrap_or(DEFAULT_HAMMING_THRESHOLD);
+    let cancel = crate::jobs::register_job(&job_id);
+    std::thread::spawn(move || {
+        match scan_and_group(&app, &job_id, &cancel, threshold) {
+            Ok(groups) => crate::jobs::emit_complete(&app, &job_id, groups),
+            Err(e) => crate::jobs::emit_error(&app, &job_id, e.to_string()),
+        }
+        crate::jobs::unregister_job(&job_id);
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgb};
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    fn mk_tempdir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "ssm-dedupe-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, AtomicOrdering::SeqCst)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    // A 32x32 horizontal brightness gradient. Ascending (left-to-right) makes
+    // every dHash bit 0 (left pixel always dimmer); descending makes every
+    // bit 1 (left pixel always brighter) — deterministic regardless of the
+    // resize filter, since the monotonic trend survives downscaling.
+    fn write_gradient(path: &std::path::Path, ascending: bool) {
+        let (w, h) = (32u32, 32u32);
+        let img = ImageBuffer::from_fn(w, h, |x, _y| {
+            let t = x as f32 / (w - 1) as f32;
+            let v = if ascending { (t * 255.0) as u8 } else { ((1.0 - t) * 255.0) as u8 };
+            Rgb([v, v, v])
+        });
+        img.save(path).unwrap();
+    }
+
+    #[test]
+    fn dhash_identical_images_are_exact_matches() {
+        let dir = mk_tempdir();
+        let p1 = dir.join("a.png");
+        let p2 = dir.join("b.png");
+        write_gradient(&p1, true);
+        write_gradient(&p2, true);
+        let h1 = dhash(&p1).unwrap();
+        let h2 = dhash(&p2).unwrap();
+        assert_eq!((h1 ^ h2).count_ones(), 0);
+    }
+
+    #[test]
+    fn dhash_inverted_gradients_are_maximally_different() {
+        let dir = mk_tempdir();
+        let ascending = dir.join("asc.png");
+        let descending = dir.join("desc.png");
+        write_gradient(&ascending, true);
+        write_gradient(&descending, false);
+
+        let h_asc = dhash(&ascending).unwrap();
+        let h_desc = dhash(&descending).unwrap();
+        assert_eq!(h_asc, 0);
+        assert_eq!(h_desc, u64::MAX);
+        assert_eq!((h_asc ^ h_desc).count_ones(), 64);
+    }
+
+    #[test]
+    fn dhash_missing_file_returns_none() {
+        assert!(dhash(&PathBuf::from("/nonexistent/not-a-screenshot.png")).is_none());
+    }
+
+    fn item(name: &str, size: u64) -> ScreenshotItem {
+        ScreenshotItem {
+            path: name.to_string(),
+            file_name: name.to_string(),
+            created_at: None,
+            modified_at: None,
+            size_bytes: Some(size),
+        }
+    }
+
+    #[test]
+    fn group_hashed_groups_within_threshold_and_drops_singletons() {
+        let hashed = vec![
+            (item("a", 10), 0b0000u64),
+            (item("b", 20), 0b0001u64), // Hamming distance 1 from "a"
+            (item("c", 5), u64::MAX),   // far from both
+        ];
+        let groups = group_hashed(hashed, 2);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+        // Largest/most-recent first within the group.
+        assert_eq!(groups[0][0].file_name, "b");
+    }
+
+    #[test]
+    fn group_hashed_respects_a_tight_threshold() {
+        let hashed = vec![(item("a", 10), 0b0000u64), (item("b", 20), 0b0001u64)];
+        assert!(group_hashed(hashed, 0).is_empty());
+    }
+}
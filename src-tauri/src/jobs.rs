@@ -0,0 +1,72 @@
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter};
+
+// Long-running commands (delete_to_trash, find_duplicate_screenshots,
+// thumbnail pre-generation) run on a worker thread and report back through
+// these events instead of blocking the invoke call.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgressData {
+    pub job_id: String,
+    pub current_stage: u32,
+    pub max_stage: u32,
+    pub files_checked: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobComplete<T: Serialize> {
+    job_id: String,
+    result: T,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JobError {
+    job_id: String,
+    message: String,
+}
+
+static CANCEL_FLAGS: Lazy<Mutex<HashMap<String, Arc<AtomicBool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Registers a job id so `cancel_job` can signal it, returning the flag the
+/// worker thread should poll each iteration.
+pub fn register_job(job_id: &str) -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    CANCEL_FLAGS.lock().insert(job_id.to_string(), flag.clone());
+    flag
+}
+
+pub fn unregister_job(job_id: &str) {
+    CANCEL_FLAGS.lock().remove(job_id);
+}
+
+/// Requests cancellation of a running job. The worker checks its cancel flag
+/// between files, so already-processed work (e.g. trashed files) is kept.
+#[tauri::command]
+pub fn cancel_job(job_id: String) -> tauri::Result<()> {
+    if let Some(flag) = CANCEL_FLAGS.lock().get(&job_id) {
+        flag.store(true, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+pub fn emit_progress(app: &AppHandle, job_id: &str, current_stage: u32, max_stage: u32, files_checked: u64) {
+    let _ = app.emit(
+        "job-progress",
+        ProgressData { job_id: job_id.to_string(), current_stage, max_stage, files_checked },
+    );
+}
+
+pub fn emit_complete<T: Serialize>(app: &AppHandle, job_id: &str, result: T) {
+    let _ = app.emit("job-complete", JobComplete { job_id: job_id.to_string(), result });
+}
+
+pub fn emit_error(app: &AppHandle, job_id: &str, message: impl Into<String>) {
+    let _ = app.emit("job-error", JobError { job_id: job_id.to_string(), message: message.into() });
+}
@@ -1,15 +1,27 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 use time::{format_description::well_known::Rfc3339, OffsetDateTime};
 
-#[derive(Debug, Serialize)]
-struct ScreenshotItem {
-    path: String,
-    file_name: String,
-    created_at: Option<String>,
-    modified_at: Option<String>,
-    size_bytes: Option<u64>,
+mod cleanup;
+mod dedupe;
+mod jobs;
+mod thumbnails;
+mod watcher;
+use cleanup::find_cleanup_candidates;
+use dedupe::find_duplicate_screenshots;
+use jobs::cancel_job;
+use thumbnails::{get_thumbnail, pregenerate_thumbnails};
+use watcher::{start_watching, stop_watching};
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct ScreenshotItem {
+    pub(crate) path: String,
+    pub(crate) file_name: String,
+    pub(crate) created_at: Option<String>,
+    pub(crate) modified_at: Option<String>,
+    pub(crate) size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -26,9 +38,20 @@ enum SortBy {
 struct ListOptions {
     sort_by: SortBy,
     descending: bool,
+    /// Root directories to scan. Defaults to just `screenshot_dir()` when empty.
+    #[serde(default)]
+    roots: Vec<String>,
+    /// Recurse into subdirectories of each root instead of a flat scan.
+    #[serde(default)]
+    recursive: bool,
+    /// Glob patterns whose matching files and subtrees are skipped. Matched
+    /// against both the bare entry name (so `*.tmp` or `*cache*` work at any
+    /// depth) and the path relative to its root (so `**/*.tmp` also works).
+    #[serde(default)]
+    exclude_globs: Vec<String>,
 }
 
-fn is_screenshot_name(file_name: &str) -> bool {
+pub(crate) fn is_screenshot_name(file_name: &str) -> bool {
     let lower = file_name.to_lowercase();
     // Common macOS screenshot prefixes and patterns
     let looks_like = lower.starts_with("screen shot ")
@@ -54,7 +77,7 @@ fn desktop_dir() -> Option<PathBuf> {
 
 // Best-effort resolution of the user's current macOS screenshot save location.
 // Falls back to Desktop if the preference is not set or invalid.
-fn screenshot_dir() -> Option<PathBuf> {
+pub(crate) fn screenshot_dir() -> Option<PathBuf> {
     // Single env override for tests/advanced users
     if let Ok(p) = std::env::var("SSM_SCREENSHOT_DIR") {
         let pb = PathBuf::from(p);
@@ -99,46 +122,117 @@ fn user_trash_dir() -> Option<PathBuf> {
     dirs::home_dir().map(|p| p.join(".Trash"))
 }
 
-#[tauri::command]
-fn list_screenshots(options: Option<ListOptions>) -> tauri::Result<Vec<ScreenshotItem>> {
+// Builds the ScreenshotItem metadata for a single path, if it still exists and
+// looks like a screenshot. Shared by the directory scanners and the watcher,
+// which needs to describe one freshly-changed file at a time.
+pub(crate) fn screenshot_item_for_path(path: &std::path::Path) -> Option<ScreenshotItem> {
+    let file_name = path.file_name().and_then(|s| s.to_str())?.to_string();
+    if !is_screenshot_name(&file_name) {
+        return None;
+    }
+    if !path.exists() {
+        return None;
+    }
+    let metadata = fs::metadata(path).ok();
+    let size_bytes = metadata.as_ref().map(|m| m.len());
+    let created_at = metadata
+        .as_ref()
+        .and_then(|m| m.created().ok())
+        .and_then(|t| OffsetDateTime::from(t).format(&Rfc3339).ok());
+    let modified_at = metadata
+        .as_ref()
+        .and_then(|m| m.modified().ok())
+        .and_then(|t| OffsetDateTime::from(t).format(&Rfc3339).ok());
+
+    Some(ScreenshotItem {
+        path: path.to_string_lossy().into_owned(),
+        file_name,
+        created_at,
+        modified_at,
+        size_bytes,
+    })
+}
+
+// Scans a single directory non-recursively for files that look like screenshots,
+// building the ScreenshotItem metadata used by the duplicate-detection and
+// cleanup commands and by the watcher's initial snapshot.
+pub(crate) fn collect_screenshot_items(dir: &PathBuf) -> std::io::Result<Vec<ScreenshotItem>> {
     let mut items: Vec<ScreenshotItem> = Vec::new();
-    let shots_dir = screenshot_dir().ok_or_else(|| anyhow::anyhow!("No screenshots directory found"))?;
-    // scan screenshots directory
-    if shots_dir.is_dir() {
-        for entry in fs::read_dir(&shots_dir).map_err(|e| anyhow::anyhow!(e))? {
-            let entry = entry.map_err(|e| anyhow::anyhow!(e))?;
+    if !dir.is_dir() {
+        return Ok(items);
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(item) = screenshot_item_for_path(&path) {
+            items.push(item);
+        }
+    }
+    Ok(items)
+}
+
+// Matches `path` (relative to `root`) against `excludes`. Checks both the
+// bare file/dir name (so a plain name like `cache` matches that entry at any
+// depth, not just directly under `root`) and the path relative to `root` (so
+// path patterns like `nested/cache` or `**/*.tmp` also work).
+fn entry_excluded(path: &std::path::Path, root: &std::path::Path, excludes: &[glob::Pattern]) -> bool {
+    let file_name = path.file_name().and_then(|s| s.to_str());
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    excludes
+        .iter()
+        .any(|pat| file_name.is_some_and(|n| pat.matches(n)) || pat.matches_path(relative))
+}
+
+// Depth-first walk of `root`, collecting screenshot files and (when
+// `recursive`) descending into subdirectories. Any entry matching one of
+// `excludes` (see `entry_excluded`) is skipped entirely, pruning excluded
+// subtrees rather than just filtering their files out afterward.
+fn walk_screenshot_dir(root: &std::path::Path, recursive: bool, excludes: &[glob::Pattern]) -> Vec<ScreenshotItem> {
+    let mut items = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(read) = fs::read_dir(&dir) else { continue };
+        for entry in read.flatten() {
             let path = entry.path();
-            if !path.is_file() {
+            if entry_excluded(&path, root, excludes) {
                 continue;
             }
-            let file_name = match path.file_name().and_then(|s| s.to_str()) {
-                Some(s) => s.to_string(),
-                None => continue,
-            };
-            if !is_screenshot_name(&file_name) {
+            if path.is_dir() {
+                if recursive {
+                    stack.push(path);
+                }
                 continue;
             }
-            let metadata = entry.metadata().ok();
-            let size_bytes = metadata.as_ref().map(|m| m.len());
-            let created_at = metadata
-                .as_ref()
-                .and_then(|m| m.created().ok())
-                .and_then(|t| OffsetDateTime::from(t).format(&Rfc3339).ok());
-            let modified_at = metadata
-                .as_ref()
-                .and_then(|m| m.modified().ok())
-                .and_then(|t| OffsetDateTime::from(t).format(&Rfc3339).ok());
-
-            items.push(ScreenshotItem {
-                path: path.to_string_lossy().into_owned(),
-                file_name,
-                created_at,
-                modified_at,
-                size_bytes,
-            });
+            if let Some(item) = screenshot_item_for_path(&path) {
+                items.push(item);
+            }
         }
     }
-    // no debug logs
+
+    items
+}
+
+#[tauri::command]
+fn list_screenshots(options: Option<ListOptions>) -> tauri::Result<Vec<ScreenshotItem>> {
+    let roots: Vec<PathBuf> = match &options {
+        Some(opts) if !opts.roots.is_empty() => opts.roots.iter().map(PathBuf::from).collect(),
+        _ => vec![screenshot_dir().ok_or_else(|| anyhow::anyhow!("No screenshots directory found"))?],
+    };
+    let recursive = options.as_ref().map(|o| o.recursive).unwrap_or(false);
+    let excludes: Vec<glob::Pattern> = options
+        .as_ref()
+        .map(|o| o.exclude_globs.iter().filter_map(|g| glob::Pattern::new(g).ok()).collect())
+        .unwrap_or_default();
+
+    // Deep trees shouldn't serialize behind each other; walk each root in parallel.
+    let mut items: Vec<ScreenshotItem> = roots
+        .par_iter()
+        .flat_map(|root| walk_screenshot_dir(root, recursive, &excludes))
+        .collect();
 
     // Sorting
     if let Some(opts) = options {
@@ -168,6 +262,63 @@ struct UndoEntry {
 static UNDO_STACK: once_cell::sync::Lazy<parking_lot::Mutex<Vec<UndoEntry>>> =
     once_cell::sync::Lazy::new(|| parking_lot::Mutex::new(Vec::new()));
 
+// Keep the undo history from growing without bound across a long-lived app.
+const MAX_UNDO_ENTRIES: usize = 500;
+const MAX_UNDO_AGE_DAYS: u64 = 30;
+
+fn undo_store_path() -> Option<PathBuf> {
+    // Single env override for tests/advanced users
+    if let Ok(p) = std::env::var("SSM_DATA_DIR") {
+        return Some(PathBuf::from(p).join("undo.json"));
+    }
+    dirs::data_dir().map(|p| p.join("ssm").join("undo.json"))
+}
+
+fn save_undo_stack(stack: &[UndoEntry]) {
+    let Some(path) = undo_store_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_vec(stack) {
+        let _ = fs::write(&path, json);
+    }
+}
+
+// Loads the persisted undo stack at startup, dropping entries that can no
+// longer be actioned: too old, or whose trashed file is gone and can't be
+// relocated by fuzzy name match.
+fn load_undo_stack() -> Vec<UndoEntry> {
+    let Some(path) = undo_store_path() else { return Vec::new() };
+    let Ok(bytes) = fs::read(&path) else { return Vec::new() };
+    let Ok(entries) = serde_json::from_slice::<Vec<UndoEntry>>(&bytes) else { return Vec::new() };
+
+    let now_ms = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let max_age_ms = MAX_UNDO_AGE_DAYS * 24 * 60 * 60 * 1000;
+    let trash_dir = user_trash_dir();
+
+    let mut valid: Vec<UndoEntry> = entries
+        .into_iter()
+        .filter(|e| now_ms.saturating_sub(e.deleted_at_ms) <= max_age_ms)
+        .filter_map(|mut e| {
+            if PathBuf::from(&e.trashed_path).exists() {
+                return Some(e);
+            }
+            let relocated = best_trash_candidate(trash_dir.as_ref()?, &e.file_name, Some(e.deleted_at_ms))?;
+            e.trashed_path = relocated.to_string_lossy().into_owned();
+            Some(e)
+        })
+        .collect();
+
+    if valid.len() > MAX_UNDO_ENTRIES {
+        let excess = valid.len() - MAX_UNDO_ENTRIES;
+        valid.drain(0..excess);
+    }
+    valid
+}
+
 #[derive(Debug, Serialize)]
 struct TrashResult { trashed: Vec<UndoEntry> }
 
@@ -216,45 +367,74 @@ fn best_trash_candidate(trash_dir: &PathBuf, original_name: &str, deleted_at_ms:
     if let Some((p, _)) = best { Some(p) } else { newest_any.map(|(p, _)| p) }
 }
 
-#[tauri::command]
-fn delete_to_trash(paths: Vec<String>) -> tauri::Result<TrashResult> {
-    let mut results: Vec<UndoEntry> = Vec::new();
-    let trash_dir = user_trash_dir()
-        .ok_or_else(|| anyhow::anyhow!("Cannot resolve user Trash directory"))?;
-
-    for p in paths {
-        let original = PathBuf::from(&p);
-        let file_name = match original.file_name().and_then(|s| s.to_str()) {
-            Some(s) => s.to_string(),
-            None => continue,
-        };
-        // capture deletion time
-        let deleted_at = std::time::SystemTime::now();
-        // move to system Trash
-        trash::delete(&original).map_err(|e| anyhow::anyhow!(e))?;
-
-        // find the trashed file path with fuzzy matching (handles name collisions)
-        let candidate = best_trash_candidate(&trash_dir, &file_name, Some(deleted_at.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64));
-        let (trashed_path, file_name_owned) = if let Some(trashed_path) = candidate {
-            (trashed_path, file_name)
-        } else {
-            (trash_dir.join(&file_name), file_name)
-        };
-
-        let entry = UndoEntry {
-            original_path: original.to_string_lossy().into_owned(),
-            trashed_path: trashed_path.to_string_lossy().into_owned(),
-            file_name: file_name_owned,
-            deleted_at_ms: deleted_at
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as u64,
-        };
-        UNDO_STACK.lock().push(entry.clone());
-        results.push(entry);
+// Moves a single file to Trash and records its UndoEntry. Shared by the
+// delete_to_trash job loop; returns Ok(None) for paths with no file name.
+fn trash_one(trash_dir: &PathBuf, p: &str) -> anyhow::Result<Option<UndoEntry>> {
+    let original = PathBuf::from(p);
+    let file_name = match original.file_name().and_then(|s| s.to_str()) {
+        Some(s) => s.to_string(),
+        None => return Ok(None),
+    };
+    // capture deletion time
+    let deleted_at = std::time::SystemTime::now();
+    // move to system Trash
+    trash::delete(&original).map_err(|e| anyhow::anyhow!(e))?;
+
+    // find the trashed file path with fuzzy matching (handles name collisions)
+    let candidate = best_trash_candidate(
+        trash_dir,
+        &file_name,
+        Some(deleted_at.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64),
+    );
+    let trashed_path = candidate.unwrap_or_else(|| trash_dir.join(&file_name));
+
+    let entry = UndoEntry {
+        original_path: original.to_string_lossy().into_owned(),
+        trashed_path: trashed_path.to_string_lossy().into_owned(),
+        file_name,
+        deleted_at_ms: deleted_at.duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64,
+    };
+    let mut stack = UNDO_STACK.lock();
+    stack.push(entry.clone());
+    if stack.len() > MAX_UNDO_ENTRIES {
+        let excess = stack.len() - MAX_UNDO_ENTRIES;
+        stack.drain(0..excess);
     }
+    save_undo_stack(&stack);
+    drop(stack);
+    Ok(Some(entry))
+}
+
+/// Moves `paths` to Trash on a worker thread, reporting progress via
+/// `job-progress` events and the final tally via `job-complete`/`job-error`
+/// (both keyed by `job_id`). Cancellable mid-run through `cancel_job`; files
+/// already trashed before cancellation stay recorded on the undo stack.
+#[tauri::command]
+fn delete_to_trash(app: tauri::AppHandle, job_id: String, paths: Vec<String>) -> tauri::Result<()> {
+    let cancel = jobs::register_job(&job_id);
+    std::thread::spawn(move || {
+        let outcome = (|| -> anyhow::Result<TrashResult> {
+            let trash_dir = user_trash_dir().ok_or_else(|| anyhow::anyhow!("Cannot resolve user Trash directory"))?;
+            let mut results: Vec<UndoEntry> = Vec::new();
+            for (i, p) in paths.iter().enumerate() {
+                if cancel.load(std::sync::atomic::Ordering::SeqCst) {
+                    break;
+                }
+                if let Some(entry) = trash_one(&trash_dir, p)? {
+                    results.push(entry);
+                }
+                jobs::emit_progress(&app, &job_id, 1, 1, (i + 1) as u64);
+            }
+            Ok(TrashResult { trashed: results })
+        })();
 
-    Ok(TrashResult { trashed: results })
+        match outcome {
+            Ok(result) => jobs::emit_complete(&app, &job_id, result),
+            Err(e) => jobs::emit_error(&app, &job_id, e.to_string()),
+        }
+        jobs::unregister_job(&job_id);
+    });
+    Ok(())
 }
 
 #[tauri::command]
@@ -264,6 +444,7 @@ fn undo_last_delete(count: Option<usize>) -> tauri::Result<Vec<UndoEntry>> {
     let n = count.unwrap_or(1).min(stack.len());
     for _ in 0..n {
         if let Some(entry) = stack.pop() {
+            save_undo_stack(&stack);
             let from = PathBuf::from(&entry.trashed_path);
             let to = PathBuf::from(&entry.original_path);
             let target = if to.exists() {
@@ -316,7 +497,23 @@ fn undo_last_delete(count: Option<usize>) -> tauri::Result<Vec<UndoEntry>> {
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![list_screenshots, delete_to_trash, undo_last_delete])
+        .invoke_handler(tauri::generate_handler![
+            list_screenshots,
+            delete_to_trash,
+            undo_last_delete,
+            find_duplicate_screenshots,
+            start_watching,
+            stop_watching,
+            get_thumbnail,
+            pregenerate_thumbnails,
+            cancel_job,
+            find_cleanup_candidates
+        ])
+        .setup(|app| {
+            *UNDO_STACK.lock() = load_undo_stack();
+            start_watching(app.handle().clone())?;
+            Ok(())
+        })
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -360,6 +557,50 @@ mod tests {
         assert!(!super::looks_like_same_file("Screenshot 2025-01-01.png", "Screenshot 2025-01-01.jpg"));
     }
 
+    #[test]
+    fn walk_screenshot_dir_recurses_and_prunes_excluded_subtrees() {
+        let root = mk_tempdir();
+        std::fs::write(root.join("Screenshot top.png"), b"a").unwrap();
+
+        let nested = root.join("nested");
+        std::fs::create_dir(&nested).unwrap();
+        std::fs::write(nested.join("Screenshot nested.png"), b"b").unwrap();
+
+        let cache = root.join("cache");
+        std::fs::create_dir(&cache).unwrap();
+        std::fs::write(cache.join("Screenshot excluded.png"), b"c").unwrap();
+
+        // Non-recursive: only the top-level file.
+        let flat = super::walk_screenshot_dir(&root, false, &[]);
+        assert_eq!(flat.len(), 1);
+        assert_eq!(flat[0].file_name, "Screenshot top.png");
+
+        // Recursive with no excludes: descends into both subdirectories.
+        let all = super::walk_screenshot_dir(&root, true, &[]);
+        let mut names: Vec<_> = all.iter().map(|i| i.file_name.clone()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Screenshot excluded.png", "Screenshot nested.png", "Screenshot top.png"]);
+
+        // Recursive with the "cache" subtree excluded: its file is pruned entirely.
+        let excludes = vec![glob::Pattern::new("cache").unwrap()];
+        let pruned = super::walk_screenshot_dir(&root, true, &excludes);
+        let mut names: Vec<_> = pruned.iter().map(|i| i.file_name.clone()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Screenshot nested.png", "Screenshot top.png"]);
+    }
+
+    #[test]
+    fn entry_excluded_matches_bare_name_and_relative_path() {
+        let root = PathBuf::from("/shots");
+        let bare = vec![glob::Pattern::new("*cache*").unwrap()];
+        assert!(super::entry_excluded(&root.join("nested/cache-dir"), &root, &bare));
+        assert!(!super::entry_excluded(&root.join("nested/keep.png"), &root, &bare));
+
+        let anchored = vec![glob::Pattern::new("**/*.tmp").unwrap()];
+        assert!(super::entry_excluded(&root.join("nested/draft.tmp"), &root, &anchored));
+        assert!(!super::entry_excluded(&root.join("nested/draft.png"), &root, &anchored));
+    }
+
     #[test]
     fn best_trash_candidate_picks_latest_like_name() {
         let trash = mk_tempdir();
@@ -397,13 +638,17 @@ mod tests {
         };
         UNDO_STACK.lock().push(entry);
 
-        // point the app's trash dir to our temp location to exercise the direct path branch
+        // point the app's trash dir to our temp location to exercise the direct path branch,
+        // and the undo store to a scratch dir so this test doesn't touch the real one
+        let data_dir = mk_tempdir();
         std::env::set_var("SSM_TRASH_DIR", &trash_td);
+        std::env::set_var("SSM_DATA_DIR", &data_dir);
         let res = super::undo_last_delete(Some(1)).unwrap();
         assert_eq!(res.len(), 1);
         assert!(orig.exists());
         assert!(!trashed.exists());
         std::env::remove_var("SSM_TRASH_DIR");
+        std::env::remove_var("SSM_DATA_DIR");
     }
 
     // NOTE: The "(restored)" collision path is exercised indirectly by logic,
@@ -421,7 +666,7 @@ mod tests {
         std::env::set_var("SSM_SCREENSHOT_DIR", &desktop);
 
         // Name ascending
-        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Name, descending: false })).unwrap();
+        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Name, descending: false, roots: vec![], recursive: false, exclude_globs: vec![] })).unwrap();
         let names: Vec<_> = items.iter().map(|i| i.file_name.clone()).collect();
         assert_eq!(names, vec![
             "Screenshot largest.png",
@@ -430,7 +675,7 @@ mod tests {
         ]);
 
         // Size descending
-        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Size, descending: true })).unwrap();
+        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Size, descending: true, roots: vec![], recursive: false, exclude_globs: vec![] })).unwrap();
         let sizes: Vec<_> = items.iter().map(|i| i.size_bytes.unwrap_or(0)).collect();
         assert_eq!(sizes, vec![3, 2, 1]);
 
@@ -438,6 +683,135 @@ mod tests {
         let _ = std::fs::remove_dir_all(desktop);
     }
 
+    fn write_undo_json(data_dir: &std::path::Path, entries: &[UndoEntry]) {
+        std::fs::create_dir_all(data_dir).unwrap();
+        std::fs::write(data_dir.join("undo.json"), serde_json::to_vec(entries).unwrap()).unwrap();
+    }
+
+    fn now_ms() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64
+    }
+
+    #[test]
+    fn load_undo_stack_drops_entries_older_than_max_age() {
+        let _guard = TEST_ENV_LOCK.lock();
+        let data_dir = mk_tempdir();
+        let trash_dir = mk_tempdir();
+        let day_ms = 24 * 60 * 60 * 1000;
+        let recent_trashed = trash_dir.join("recent-trashed.png");
+        std::fs::write(&recent_trashed, b"img").unwrap();
+        let recent = UndoEntry {
+            original_path: "/tmp/recent.png".to_string(),
+            trashed_path: recent_trashed.to_string_lossy().into_owned(),
+            file_name: "recent.png".to_string(),
+            deleted_at_ms: now_ms() - 1 * day_ms,
+        };
+        // The stale entry's trashed file is deliberately never created: it
+        // must be dropped by the age cutoff alone, not the missing-file path.
+        let stale = UndoEntry {
+            original_path: "/tmp/stale.png".to_string(),
+            trashed_path: trash_dir.join("stale-trashed.png").to_string_lossy().into_owned(),
+            file_name: "stale.png".to_string(),
+            deleted_at_ms: now_ms() - (MAX_UNDO_AGE_DAYS + 1) * day_ms,
+        };
+        write_undo_json(&data_dir, &[recent.clone(), stale]);
+
+        std::env::set_var("SSM_DATA_DIR", &data_dir);
+        std::env::set_var("SSM_TRASH_DIR", &trash_dir);
+        let loaded = super::load_undo_stack();
+        std::env::remove_var("SSM_DATA_DIR");
+        std::env::remove_var("SSM_TRASH_DIR");
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].file_name, recent.file_name);
+    }
+
+    #[test]
+    fn load_undo_stack_relocates_entry_whose_trashed_file_moved() {
+        let _guard = TEST_ENV_LOCK.lock();
+        let data_dir = mk_tempdir();
+        let trash_dir = mk_tempdir();
+
+        // The recorded trashed_path no longer exists, but a like-named file
+        // sits in the (possibly new) Trash location.
+        let relocated = trash_dir.join("Screenshot 2025-01-01 at 1.23.45 AM.png");
+        std::fs::write(&relocated, b"img").unwrap();
+
+        let entry = UndoEntry {
+            original_path: "/tmp/Screenshot 2025-01-01 at 1.23.45 AM.png".to_string(),
+            trashed_path: trash_dir.join("gone.png").to_string_lossy().into_owned(),
+            file_name: "Screenshot 2025-01-01 at 1.23.45 AM.png".to_string(),
+            deleted_at_ms: now_ms(),
+        };
+        write_undo_json(&data_dir, &[entry]);
+
+        std::env::set_var("SSM_DATA_DIR", &data_dir);
+        std::env::set_var("SSM_TRASH_DIR", &trash_dir);
+        let loaded = super::load_undo_stack();
+        std::env::remove_var("SSM_DATA_DIR");
+        std::env::remove_var("SSM_TRASH_DIR");
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].trashed_path, relocated.to_string_lossy());
+    }
+
+    #[test]
+    fn load_undo_stack_drops_entry_whose_trashed_file_is_unrecoverable() {
+        let _guard = TEST_ENV_LOCK.lock();
+        let data_dir = mk_tempdir();
+        let trash_dir = mk_tempdir();
+
+        let entry = UndoEntry {
+            original_path: "/tmp/missing.png".to_string(),
+            trashed_path: trash_dir.join("missing.png").to_string_lossy().into_owned(),
+            file_name: "missing.png".to_string(),
+            deleted_at_ms: now_ms(),
+        };
+        write_undo_json(&data_dir, &[entry]);
+
+        std::env::set_var("SSM_DATA_DIR", &data_dir);
+        std::env::set_var("SSM_TRASH_DIR", &trash_dir);
+        let loaded = super::load_undo_stack();
+        std::env::remove_var("SSM_DATA_DIR");
+        std::env::remove_var("SSM_TRASH_DIR");
+
+        assert!(loaded.is_empty());
+    }
+
+    #[test]
+    fn load_undo_stack_caps_at_max_undo_entries_keeping_newest() {
+        let _guard = TEST_ENV_LOCK.lock();
+        let data_dir = mk_tempdir();
+        let trash_dir = mk_tempdir();
+        let entries: Vec<UndoEntry> = (0..MAX_UNDO_ENTRIES + 5)
+            .map(|i| {
+                let trashed_path = trash_dir.join(format!("trashed-{i}.png"));
+                std::fs::write(&trashed_path, b"img").unwrap();
+                UndoEntry {
+                    original_path: format!("/tmp/{i}.png"),
+                    trashed_path: trashed_path.to_string_lossy().into_owned(),
+                    file_name: format!("{i}.png"),
+                    deleted_at_ms: now_ms(),
+                }
+            })
+            .collect();
+        write_undo_json(&data_dir, &entries);
+
+        std::env::set_var("SSM_DATA_DIR", &data_dir);
+        std::env::set_var("SSM_TRASH_DIR", &trash_dir);
+        let loaded = super::load_undo_stack();
+        std::env::remove_var("SSM_DATA_DIR");
+        std::env::remove_var("SSM_TRASH_DIR");
+
+        assert_eq!(loaded.len(), MAX_UNDO_ENTRIES);
+        // Oldest (lowest-index) entries are the ones trimmed.
+        assert_eq!(loaded[0].file_name, "5.png");
+        assert_eq!(loaded.last().unwrap().file_name, format!("{}.png", MAX_UNDO_ENTRIES + 4));
+    }
+
     #[test]
     fn list_screenshots_reads_from_overridden_desktop() {
         let _guard = TEST_ENV_LOCK.lock();
@@ -447,7 +821,7 @@ mod tests {
         std::fs::write(desktop.join("Screenshot 2025-01-01 at 1.23.45 AM.png"), b"x").unwrap();
         std::env::set_var("SSM_SCREENSHOT_DIR", &desktop);
 
-        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Name, descending: false }))
+        let items = super::list_screenshots(Some(ListOptions { sort_by: SortBy::Name, descending: false, roots: vec![], recursive: false, exclude_globs: vec![] }))
             .expect("ok");
         assert_eq!(items.len(), 1);
         assert!(items[0].file_name.starts_with("Screenshot"));
@@ -0,0 +1,158 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::imageops::FilterType;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// LRU budget for the on-disk thumbnail cache; oldest-accessed thumbnails are
+// evicted first once the cache grows past this.
+const DEFAULT_MAX_CACHE_BYTES: u64 = 500 * 1024 * 1024;
+
+fn thumb_cache_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|p| p.join("ssm").join("thumbs"))
+}
+
+// Cache key derived from (size, mtime, max_dim) rather than the full file
+// bytes, so a cache hit only costs a stat, not a full-resolution read. This
+// trades away dedup of byte-identical files with different mtimes (each gets
+// its own cached thumbnail) for cheap repeat lookups of the same file, which
+// is the common case (the grid re-requesting thumbnails it already has). A
+// wide (256-bit) digest keeps collisions practically impossible, unlike a
+// 64-bit std hasher.
+fn cache_key(meta: &std::fs::Metadata, max_dim: u32) -> Option<String> {
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let mut buf = Vec::with_capacity(24);
+    buf.extend_from_slice(&meta.len().to_le_bytes());
+    buf.extend_from_slice(&mtime.as_secs().to_le_bytes());
+    buf.extend_from_slice(&mtime.subsec_nanos().to_le_bytes());
+    buf.extend_from_slice(&max_dim.to_le_bytes());
+    Some(blake3::hash(&buf).to_hex().to_string())
+}
+
+// Bumps the thumbnail's mtime so the LRU eviction sees it as recently used.
+fn touch(path: &Path) {
+    let now = filetime::FileTime::now();
+    let _ = filetime::set_file_mtime(path, now);
+}
+
+fn generate_thumbnail(src: &Path, cache_dir: &Path, max_dim: u32) -> std::io::Result<PathBuf> {
+    let meta = fs::metadata(src)?;
+    let key = cache_key(&meta, max_dim)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "source file has no mtime"))?;
+    let dest = cache_dir.join(format!("{key}.webp"));
+    if dest.exists() {
+        touch(&dest);
+        return Ok(dest);
+    }
+
+    // Only a cache miss pays for the full-resolution read; it's unavoidable
+    // here since generating the thumbnail requires decoding the image.
+    let bytes = fs::read(src)?;
+    fs::create_dir_all(cache_dir)?;
+    let img = image::load_from_memory(&bytes)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    let (w, h) = (img.width().max(1), img.height().max(1));
+    let longest = w.max(h);
+    let scale = (max_dim as f32 / longest as f32).min(1.0);
+    let new_w = ((w as f32 * scale).round() as u32).max(1);
+    let new_h = ((h as f32 * scale).round() as u32).max(1);
+
+    let thumb = img.resize(new_w, new_h, FilterType::Triangle);
+    thumb
+        .save_with_format(&dest, image::ImageFormat::WebP)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+
+    evict_if_over_budget(cache_dir, DEFAULT_MAX_CACHE_BYTES);
+    Ok(dest)
+}
+
+fn evict_if_over_budget(cache_dir: &Path, budget_bytes: u64) {
+    let Ok(read) = fs::read_dir(cache_dir) else { return };
+    let mut entries: Vec<(PathBuf, u64, std::time::SystemTime)> = Vec::new();
+    let mut total: u64 = 0;
+    for entry in read.flatten() {
+        let Ok(meta) = entry.metadata() else { continue };
+        if !meta.is_file() {
+            continue;
+        }
+        let mtime = meta.modified().unwrap_or(std::time::SystemTime::UNIX_EPOCH);
+        total += meta.len();
+        entries.push((entry.path(), meta.len(), mtime));
+    }
+    if total <= budget_bytes {
+        return;
+    }
+
+    // Oldest access (mtime, bumped by `touch` on every cache hit) first.
+    entries.sort_by_key(|(_, _, mtime)| *mtime);
+    for (path, size, _) in entries {
+        if total <= budget_bytes {
+            break;
+        }
+        if fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(size);
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum ThumbnailResponse {
+    Path { path: String },
+    Inline { base64: String },
+}
+
+/// Returns a cached thumbnail for `path`, generating and caching one if needed.
+/// The thumbnail is scaled down so its longest side is `max_dim`, preserving
+/// aspect ratio; images already within `max_dim` keep their size (they are not
+/// upscaled). By default the cached file path is returned for the webview to load
+/// directly; pass `inline: true` to get the encoded bytes instead.
+#[tauri::command]
+pub fn get_thumbnail(path: String, max_dim: u32, inline: Option<bool>) -> tauri::Result<ThumbnailResponse> {
+    let cache_dir =
+        thumb_cache_dir().ok_or_else(|| anyhow::anyhow!("Cannot resolve thumbnail cache directory"))?;
+    let dest = generate_thumbnail(Path::new(&path), &cache_dir, max_dim).map_err(|e| anyhow::anyhow!(e))?;
+
+    if inline.unwrap_or(false) {
+        let bytes = fs::read(&dest).map_err(|e| anyhow::anyhow!(e))?;
+        Ok(ThumbnailResponse::Inline { base64: STANDARD.encode(bytes) })
+    } else {
+        Ok(ThumbnailResponse::Path { path: dest.to_string_lossy().into_owned() })
+    }
+}
+
+/// Warms the thumbnail cache for a batch of paths in parallel on a worker
+/// thread, so the initial grid population doesn't wait on thumbnails being
+/// generated one at a time. Reports progress via `job-progress` and
+/// completion via `job-complete` (both keyed by `job_id`); cancellable
+/// through `cancel_job`.
+#[tauri::command]
+pub fn pregenerate_thumbnails(app: tauri::AppHandle, job_id: String, paths: Vec<String>, max_dim: u32) -> tauri::Result<()> {
+    let cancel = crate::jobs::register_job(&job_id);
+    std::thread::spawn(move || {
+        let outcome = (|| -> anyhow::Result<u64> {
+            let cache_dir = thumb_cache_dir()
+                .ok_or_else(|| anyhow::anyhow!("Cannot resolve thumbnail cache directory"))?;
+            let total = paths.len() as u64;
+            let checked = std::sync::atomic::AtomicU64::new(0);
+            paths.par_iter().for_each(|p| {
+                if cancel.load(std::sync::atomic::Ordering::SeqCst) {
+                    return;
+                }
+                let _ = generate_thumbnail(Path::new(p), &cache_dir, max_dim);
+                let n = checked.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                crate::jobs::emit_progress(&app, &job_id, 1, 1, n);
+            });
+            Ok(total.min(checked.load(std::sync::atomic::Ordering::SeqCst)))
+        })();
+
+        match outcome {
+            Ok(count) => crate::jobs::emit_complete(&app, &job_id, count),
+            Err(e) => crate::jobs::emit_error(&app, &job_id, e.to_string()),
+        }
+        crate::jobs::unregister_job(&job_id);
+    });
+    Ok(())
+}
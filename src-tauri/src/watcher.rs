@@ -0,0 +1,192 @@
+use crate::{screenshot_dir, screenshot_item_for_path, ScreenshotItem};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+// Screenshots often land as a temp file that's immediately renamed, so coalesce
+// bursts of events on the same path into one emission.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+// How often to re-check the `com.apple.screencapture location` preference and
+// retry creating the underlying notify watcher if the directory was missing.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    join: JoinHandle<()>,
+}
+
+static WATCH_HANDLE: Lazy<Mutex<Option<WatchHandle>>> = Lazy::new(|| Mutex::new(None));
+
+enum PendingKind {
+    AddedOrModified,
+    Removed,
+    RenamedFrom(PathBuf),
+}
+
+struct PendingEvent {
+    kind: PendingKind,
+    seen_at: Instant,
+}
+
+/// Starts a background filesystem watcher over `screenshot_dir()`, emitting
+/// `screenshot-added` / `screenshot-removed` / `screenshot-renamed` events with a
+/// `ScreenshotItem` payload as screenshots come and go. A no-op if already running.
+#[tauri::command]
+pub fn start_watching(app: AppHandle) -> tauri::Result<()> {
+    let mut handle = WATCH_HANDLE.lock();
+    if handle.is_some() {
+        return Ok(());
+    }
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let thread_stop = stop.clone();
+    let join = std::thread::spawn(move || watch_loop(app, thread_stop));
+    *handle = Some(WatchHandle { stop, join });
+    Ok(())
+}
+
+/// Stops the background watcher started by `start_watching`. A no-op if it
+/// isn't running.
+#[tauri::command]
+pub fn stop_watching() -> tauri::Result<()> {
+    if let Some(handle) = WATCH_HANDLE.lock().take() {
+        handle.stop.store(true, Ordering::SeqCst);
+        let _ = handle.join.join();
+    }
+    Ok(())
+}
+
+fn make_watcher(dir: &PathBuf, tx: mpsc::Sender<notify::Result<Event>>) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |res| {
+        let _ = tx.send(res);
+    })
+    .ok()?;
+    watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+    Some(watcher)
+}
+
+fn watch_loop(app: AppHandle, stop: Arc<AtomicBool>) {
+    let (tx, rx) = mpsc::channel::<notify::Result<Event>>();
+    let mut watched_dir = screenshot_dir();
+    let mut watcher = watched_dir.as_ref().and_then(|d| make_watcher(d, tx.clone()));
+    let mut last_recheck = Instant::now();
+
+    // Path -> last known item, so a "removed" event can still carry the
+    // metadata the frontend last saw rather than nothing at all.
+    let mut known: HashMap<PathBuf, ScreenshotItem> = HashMap::new();
+    if let Some(dir) = &watched_dir {
+        if let Ok(items) = crate::collect_screenshot_items(dir) {
+            for item in items {
+                known.insert(PathBuf::from(&item.path), item);
+            }
+        }
+    }
+
+    let mut pending: HashMap<PathBuf, PendingEvent> = HashMap::new();
+
+    while !stop.load(Ordering::SeqCst) {
+        // Re-resolve the screenshot directory periodically: the user may have
+        // changed `com.apple.screencapture location`, or the directory may
+        // have been deleted and recreated out from under the watcher. Gated
+        // on the timer alone (not `watcher.is_none()`) so a directory that
+        // stays unwatchable doesn't spawn a `defaults read` probe on every
+        // loop turn.
+        if last_recheck.elapsed() >= RECHECK_INTERVAL {
+            last_recheck = Instant::now();
+            let resolved = screenshot_dir();
+            if resolved != watched_dir || watcher.is_none() {
+                watched_dir = resolved;
+                watcher = watched_dir.as_ref().and_then(|d| make_watcher(d, tx.clone()));
+            }
+        }
+
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(Ok(event)) => record_event(&event, &mut pending),
+            Ok(Err(_)) => {
+                // The watched directory likely disappeared; drop the watcher so
+                // the next timed recheck recreates it.
+                watcher = None;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+
+        flush_due(&app, &mut pending, &mut known, DEBOUNCE);
+    }
+
+    flush_due(&app, &mut pending, &mut known, Duration::ZERO);
+}
+
+fn record_event(event: &Event, pending: &mut HashMap<PathBuf, PendingEvent>) {
+    use notify::event::{ModifyKind, RenameMode};
+
+    // A same-event rename carries both the old and new path; drop any pending
+    // event on the old path and queue a single rename on the new one rather
+    // than debouncing it as a remove+add pair.
+    if let EventKind::Modify(ModifyKind::Name(RenameMode::Both)) = event.kind {
+        if let [from, to] = event.paths.as_slice() {
+            pending.remove(from);
+            pending.insert(
+                to.clone(),
+                PendingEvent { kind: PendingKind::RenamedFrom(from.clone()), seen_at: Instant::now() },
+            );
+        }
+        return;
+    }
+
+    let new_kind = match event.kind {
+        EventKind::Create(_) | EventKind::Modify(_) => PendingKind::AddedOrModified,
+        EventKind::Remove(_) => PendingKind::Removed,
+        _ => return,
+    };
+    for path in &event.paths {
+        let kind = match &new_kind {
+            PendingKind::AddedOrModified => PendingKind::AddedOrModified,
+            PendingKind::Removed => PendingKind::Removed,
+            PendingKind::RenamedFrom(p) => PendingKind::RenamedFrom(p.clone()),
+        };
+        pending.insert(path.clone(), PendingEvent { kind, seen_at: Instant::now() });
+    }
+}
+
+fn flush_due(
+    app: &AppHandle,
+    pending: &mut HashMap<PathBuf, PendingEvent>,
+    known: &mut HashMap<PathBuf, ScreenshotItem>,
+    max_age: Duration,
+) {
+    let due: Vec<PathBuf> = pending
+        .iter()
+        .filter(|(_, ev)| ev.seen_at.elapsed() >= max_age)
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    for path in due {
+        let Some(ev) = pending.remove(&path) else { continue };
+        match ev.kind {
+            PendingKind::AddedOrModified => {
+                let Some(item) = screenshot_item_for_path(&path) else { continue };
+                known.insert(path.clone(), item.clone());
+                let _ = app.emit("screenshot-added", item);
+            }
+            PendingKind::Removed => {
+                if let Some(item) = known.remove(&path) {
+                    let _ = app.emit("screenshot-removed", item);
+                }
+            }
+            PendingKind::RenamedFrom(from) => {
+                known.remove(&from);
+                let Some(item) = screenshot_item_for_path(&path) else { continue };
+                known.insert(path.clone(), item.clone());
+                let _ = app.emit("screenshot-renamed", item);
+            }
+        }
+    }
+}